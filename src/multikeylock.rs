@@ -1,26 +1,64 @@
-use dashmap::DashMap;
+use dashmap::{mapref::entry::Entry, DashMap};
 use std::{
-    cmp::min,
+    collections::{HashMap, HashSet},
+    future::{poll_fn, Future},
     sync::{
         atomic::{AtomicU64, Ordering},
         Arc,
     },
     time::Duration,
 };
-use tokio::{select, time::sleep};
-use tokio_util::sync::CancellationToken;
+use tokio::{
+    select,
+    sync::{mpsc, Notify},
+    time::sleep,
+};
+use tokio_util::{
+    sync::CancellationToken,
+    time::{delay_queue, DelayQueue},
+};
 
 const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
-const DEFAULT_RETRY: Duration = Duration::from_millis(10);
-const MAX_BACKOFF: Duration = Duration::from_secs(1);
+
+// Bounded fallback wait used alongside the `Notify` wakeup: if a release
+// somehow races a wakeup out from under us, we re-check the map at least
+// this often instead of sleeping on `notified()` forever.
+const NOTIFY_FALLBACK: Duration = Duration::from_secs(1);
 
 static GLOBAL_COUNTER: AtomicU64 = AtomicU64::new(0);
 
+/// Holder state for a single key: either one exclusive writer, or any
+/// number of concurrent readers identified by their guards' token ids.
+/// `Shared` also tracks the token ids of writers parked waiting for it --
+/// this is what makes the lock write-preferring: once a writer is
+/// waiting, new readers wait behind it too instead of joining and
+/// starving it out under sustained read traffic.
+#[derive(Debug)]
+pub enum LockState {
+    Exclusive {
+        token_id: u64,
+        notify: Arc<Notify>,
+    },
+    Shared {
+        token_ids: HashSet<u64>,
+        waiting_writers: HashSet<u64>,
+        notify: Arc<Notify>,
+    },
+}
+
+impl LockState {
+    fn notify(&self) -> Arc<Notify> {
+        match self {
+            LockState::Exclusive { notify, .. } => notify.clone(),
+            LockState::Shared { notify, .. } => notify.clone(),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct Config {
-    pub map: DashMap<String, u64>,
+    pub map: DashMap<String, LockState>,
     pub timeout: Option<Duration>,
-    pub retry: Option<Duration>,
 }
 
 impl Default for Config {
@@ -28,16 +66,22 @@ impl Default for Config {
         Self {
             map: DashMap::new(),
             timeout: Some(DEFAULT_TIMEOUT),
-            retry: Some(DEFAULT_RETRY),
         }
     }
 }
 
 #[derive(Debug)]
 pub struct MultiKeyLock {
-    locks: Arc<DashMap<String, u64>>,
+    locks: Arc<DashMap<String, LockState>>,
     pub timeout: Duration,
-    pub retry: Duration,
+    reaper_commands: mpsc::UnboundedSender<ReaperCommand>,
+    reaper_task: tokio::task::JoinHandle<()>,
+}
+
+impl Drop for MultiKeyLock {
+    fn drop(&mut self) {
+        self.reaper_task.abort();
+    }
 }
 
 impl MultiKeyLock {
@@ -46,13 +90,42 @@ impl MultiKeyLock {
     }
 
     pub fn with_config(config: Config) -> Self {
+        let locks = Arc::new(config.map);
+        let (reaper_commands, command_rx) = mpsc::unbounded_channel();
+        let reaper_task = tokio::spawn(run_reaper(locks.clone(), command_rx));
+
         MultiKeyLock {
-            locks: Arc::new(config.map),
+            locks,
             timeout: config.timeout.unwrap_or_else(|| DEFAULT_TIMEOUT),
-            retry: config.retry.unwrap_or_else(|| DEFAULT_RETRY),
+            reaper_commands,
+            reaper_task,
         }
     }
 
+    /// Acquires the key like [`MultiKeyLock::lock`], but binds it to a
+    /// lease: if the returned guard is never dropped (its owning task
+    /// panics or is aborted), the reaper evicts the holder's token once
+    /// `ttl` elapses so the key cannot be wedged forever. Call
+    /// [`KeyLock::renew`] to push the deadline back before it expires.
+    pub async fn lock_with_lease<K: Into<String>>(&self, key: K, ttl: Duration) -> Option<KeyLock> {
+        let mut guard = self.lock(key).await?;
+
+        self.reaper_commands
+            .send(ReaperCommand::Insert {
+                key: guard.key.clone(),
+                token_id: guard.token_id,
+                ttl,
+            })
+            .ok()?;
+
+        guard.lease = Some(Lease {
+            token_id: guard.token_id,
+            commands: self.reaper_commands.clone(),
+        });
+
+        Some(guard)
+    }
+
     pub async fn lock<K: Into<String>>(&self, key: K) -> Option<KeyLock> {
         self.lock_with_timeout(key, self.timeout).await
     }
@@ -83,55 +156,572 @@ impl MultiKeyLock {
         let key: String = key.into();
         let token_id = GLOBAL_COUNTER.fetch_add(1, Ordering::SeqCst);
 
-        let mut retry = self.retry;
-
         loop {
-            let loaded = self.locks.entry(key.clone()).or_insert(token_id);
-            if *loaded == token_id {
-                return Some(KeyLock {
+            // The `Entry` borrow must not cross an await point -- holding
+            // it there would keep the shard locked and starve the
+            // holder's own `Drop` if it runs on the same task set.
+            let notify = match self.locks.entry(key.clone()) {
+                Entry::Vacant(e) => {
+                    e.insert(LockState::Exclusive {
+                        token_id,
+                        notify: Arc::new(Notify::new()),
+                    });
+                    return Some(KeyLock {
+                        map: self.locks.clone(),
+                        key,
+                        token_id,
+                        lease: None,
+                    });
+                }
+                Entry::Occupied(mut e) => {
+                    // A `Shared` entry can outlive its last reader: `Drop`
+                    // leaves it in place, reader-less, as long as writers
+                    // are still parked on it (see `ReadKeyLock::drop`). If
+                    // we're looking at one of those, there's nothing left
+                    // to wait for -- take it over directly instead of
+                    // re-registering as a waiter on a key nobody holds.
+                    let reader_less = matches!(
+                        e.get(),
+                        LockState::Shared { token_ids, .. } if token_ids.is_empty()
+                    );
+                    if reader_less {
+                        e.insert(LockState::Exclusive {
+                            token_id,
+                            notify: Arc::new(Notify::new()),
+                        });
+                        return Some(KeyLock {
+                            map: self.locks.clone(),
+                            key,
+                            token_id,
+                            lease: None,
+                        });
+                    }
+
+                    // Otherwise, mark ourselves as a waiting writer so
+                    // `Shared` stops admitting new readers while we're
+                    // parked -- otherwise sustained read traffic could
+                    // keep the key occupied forever and starve this
+                    // writer out. Idempotent across retries of the same
+                    // `token_id`.
+                    match e.get_mut() {
+                        LockState::Shared {
+                            waiting_writers,
+                            notify,
+                            ..
+                        } => {
+                            waiting_writers.insert(token_id);
+                            notify.clone()
+                        }
+                        LockState::Exclusive { notify, .. } => notify.clone(),
+                    }
+                }
+            };
+
+            // `notify_waiters()` (used by `Drop`) doesn't buffer a permit
+            // like `notify_one()` would -- it only wakes futures already
+            // registered as waiters. `Notified::enable()` registers this
+            // one synchronously, right here, so a release that lands
+            // between our failed attempt above and the `select!` below
+            // can't be missed; without it, registration wouldn't happen
+            // until the future is first polled inside `select!`, leaving
+            // a window across threads where the wakeup is lost and we
+            // fall back to the 1s `NOTIFY_FALLBACK`.
+            let notified = notify.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+
+            // Re-read the entry now that we're registered: a release that
+            // landed after the entry borrow above was dropped but before
+            // `notified()` was created woke nobody, so retry right away
+            // instead of parking if the holder we saw is already gone.
+            let released = match self.locks.get(&key) {
+                None => true,
+                Some(state) => {
+                    !Arc::ptr_eq(&state.notify(), &notify)
+                        || matches!(&*state, LockState::Shared { token_ids, .. } if token_ids.is_empty())
+                }
+            };
+            if released {
+                continue;
+            }
+
+            select! {
+                _ = cancel.cancelled() => {
+                    self.unmark_waiting_writer(&key, token_id);
+                    return None;
+                },
+                _ = notified => {},
+                _ = sleep(NOTIFY_FALLBACK) => {},
+            }
+        }
+    }
+
+    /// Clears `token_id` from a key's waiting-writer set, if it's still
+    /// present. Called when a writer gives up (cancelled or timed out)
+    /// while parked behind readers, so it doesn't keep blocking new
+    /// readers after it's no longer waiting.
+    fn unmark_waiting_writer(&self, key: &str, token_id: u64) {
+        if let Entry::Occupied(mut e) = self.locks.entry(key.to_string()) {
+            if let LockState::Shared {
+                waiting_writers, ..
+            } = e.get_mut()
+            {
+                waiting_writers.remove(&token_id);
+            }
+        }
+    }
+
+    pub fn try_lock_now<K: Into<String>>(&self, key: K) -> Option<KeyLock> {
+        let key: String = key.into();
+        let token_id = GLOBAL_COUNTER.fetch_add(1, Ordering::SeqCst);
+
+        match self.locks.entry(key.clone()) {
+            Entry::Vacant(e) => {
+                e.insert(LockState::Exclusive {
+                    token_id,
+                    notify: Arc::new(Notify::new()),
+                });
+                Some(KeyLock {
                     map: self.locks.clone(),
                     key,
                     token_id,
-                });
+                    lease: None,
+                })
+            }
+            Entry::Occupied(_) => None,
+        }
+    }
+
+    pub async fn write_lock<K: Into<String>>(&self, key: K) -> Option<WriteKeyLock> {
+        self.lock(key).await
+    }
+
+    pub async fn write_lock_with_timeout<K: Into<String>>(
+        &self,
+        key: K,
+        timeout: Duration,
+    ) -> Option<WriteKeyLock> {
+        self.lock_with_timeout(key, timeout).await
+    }
+
+    pub fn try_write_now<K: Into<String>>(&self, key: K) -> Option<WriteKeyLock> {
+        self.try_lock_now(key)
+    }
+
+    pub async fn read_lock<K: Into<String>>(&self, key: K) -> Option<ReadKeyLock> {
+        self.read_lock_with_timeout(key, self.timeout).await
+    }
+
+    pub async fn read_lock_with_timeout<K: Into<String>>(
+        &self,
+        key: K,
+        timeout: Duration,
+    ) -> Option<ReadKeyLock> {
+        let cancel = CancellationToken::new();
+        let cancel_clone = cancel.clone();
+        let handle = tokio::spawn(async move {
+            sleep(timeout).await;
+            cancel_clone.cancel();
+        });
+
+        let result = self.read_lock_with_token(key, cancel).await;
+        handle.abort();
+
+        result
+    }
+
+    async fn read_lock_with_token<K: Into<String>>(
+        &self,
+        key: K,
+        cancel: CancellationToken,
+    ) -> Option<ReadKeyLock> {
+        let key: String = key.into();
+        let token_id = GLOBAL_COUNTER.fetch_add(1, Ordering::SeqCst);
+
+        loop {
+            let notify = match self.locks.entry(key.clone()) {
+                Entry::Vacant(e) => {
+                    e.insert(LockState::Shared {
+                        token_ids: HashSet::from([token_id]),
+                        waiting_writers: HashSet::new(),
+                        notify: Arc::new(Notify::new()),
+                    });
+                    return Some(ReadKeyLock {
+                        map: self.locks.clone(),
+                        key,
+                        token_id,
+                    });
+                }
+                // A waiting writer blocks new readers from joining --
+                // they wait behind it like they would behind an
+                // `Exclusive` holder, so the writer can't be starved by
+                // a steady stream of new readers.
+                Entry::Occupied(mut e) => match e.get_mut() {
+                    LockState::Shared {
+                        token_ids,
+                        waiting_writers,
+                        notify,
+                    } if waiting_writers.is_empty() => {
+                        token_ids.insert(token_id);
+                        return Some(ReadKeyLock {
+                            map: self.locks.clone(),
+                            key,
+                            token_id,
+                        });
+                    }
+                    LockState::Shared { notify, .. } => notify.clone(),
+                    LockState::Exclusive { notify, .. } => notify.clone(),
+                },
+            };
+
+            // See the matching comment in `lock_with_token`: `enable()`
+            // registers this waiter immediately so a release racing in
+            // from another thread can't slip past unnoticed.
+            let notified = notify.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+
+            // Same re-read as in `lock_with_token`: retry at once if the
+            // blocking holder or waiting writer went away before we
+            // registered above.
+            let released = match self.locks.get(&key) {
+                None => true,
+                Some(state) => {
+                    !Arc::ptr_eq(&state.notify(), &notify)
+                        || matches!(&*state, LockState::Shared { waiting_writers, .. } if waiting_writers.is_empty())
+                }
+            };
+            if released {
+                continue;
             }
 
             select! {
                 _ = cancel.cancelled() => {
                     return None;
                 },
-                _ = sleep(retry) => {
-                    retry = min(retry * 2, MAX_BACKOFF);
-                },
+                _ = notified => {},
+                _ = sleep(NOTIFY_FALLBACK) => {},
             }
         }
     }
 
-    pub fn try_lock_now<K: Into<String>>(&self, key: K) -> Option<KeyLock> {
+    pub fn try_read_now<K: Into<String>>(&self, key: K) -> Option<ReadKeyLock> {
         let key: String = key.into();
         let token_id = GLOBAL_COUNTER.fetch_add(1, Ordering::SeqCst);
 
-        let loaded = self.locks.entry(key.clone()).or_insert(token_id);
-        if *loaded == token_id {
-            return Some(KeyLock {
-                map: self.locks.clone(),
-                key,
-                token_id,
-            });
+        match self.locks.entry(key.clone()) {
+            Entry::Vacant(e) => {
+                e.insert(LockState::Shared {
+                    token_ids: HashSet::from([token_id]),
+                    waiting_writers: HashSet::new(),
+                    notify: Arc::new(Notify::new()),
+                });
+                Some(ReadKeyLock {
+                    map: self.locks.clone(),
+                    key,
+                    token_id,
+                })
+            }
+            Entry::Occupied(mut e) => match e.get_mut() {
+                LockState::Shared {
+                    token_ids,
+                    waiting_writers,
+                    ..
+                } if waiting_writers.is_empty() => {
+                    token_ids.insert(token_id);
+                    Some(ReadKeyLock {
+                        map: self.locks.clone(),
+                        key,
+                        token_id,
+                    })
+                }
+                // Either a writer holds the key, or one is waiting on
+                // it -- in both cases a new reader doesn't join.
+                LockState::Shared { .. } | LockState::Exclusive { .. } => None,
+            },
+        }
+    }
+
+    /// Acquires every key in `keys` atomically: once this returns `Some`,
+    /// either all of them are held or none are. Keys are deduplicated and
+    /// sorted before acquisition, so every caller takes them in the same
+    /// total order -- the classic deadlock-avoidance discipline, since no
+    /// two callers can ever hold a prefix of each other's key sets while
+    /// waiting on the next one.
+    ///
+    /// Acquisition shares one `timeout` across the whole set: if any key
+    /// can't be acquired within it, every key already held is released
+    /// (in reverse order) and `None` is returned.
+    pub async fn lock_many<I: IntoIterator<Item = String>>(
+        &self,
+        keys: I,
+        timeout: Duration,
+    ) -> Option<MultiGuard> {
+        let mut sorted: Vec<String> = keys.into_iter().collect();
+        sorted.sort();
+        sorted.dedup();
+
+        let cancel = CancellationToken::new();
+        let cancel_clone = cancel.clone();
+        let handle = tokio::spawn(async move {
+            sleep(timeout).await;
+            cancel_clone.cancel();
+        });
+
+        let mut guards = Vec::with_capacity(sorted.len());
+        for key in sorted {
+            match self.lock_with_token(key, cancel.clone()).await {
+                Some(guard) => guards.push(guard),
+                None => {
+                    handle.abort();
+                    drop(MultiGuard { guards });
+                    return None;
+                }
+            }
         }
+        handle.abort();
 
-        None
+        Some(MultiGuard { guards })
+    }
+
+    /// Runs `f` while holding an exclusive lock on `key`, then releases
+    /// it and returns `f`'s result. Acquires via [`MultiKeyLock::lock`],
+    /// so it shares the instance's default `timeout`; returns `None` if
+    /// the lock couldn't be acquired in time. Scoping the guard to
+    /// exactly this call removes the common footgun of holding it across
+    /// an unrelated `.await`.
+    pub async fn with_lock<K, F, Fut, T>(&self, key: K, f: F) -> Option<T>
+    where
+        K: Into<String>,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = T>,
+    {
+        self.with_lock_timeout(key, self.timeout, f).await
+    }
+
+    /// Like [`MultiKeyLock::with_lock`], but acquires with an explicit
+    /// `timeout` instead of the instance's default.
+    pub async fn with_lock_timeout<K, F, Fut, T>(
+        &self,
+        key: K,
+        timeout: Duration,
+        f: F,
+    ) -> Option<T>
+    where
+        K: Into<String>,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = T>,
+    {
+        let guard = self.lock_with_timeout(key, timeout).await?;
+        let result = f().await;
+        drop(guard);
+        Some(result)
+    }
+
+    /// Like [`MultiKeyLock::with_lock`], but acquires via
+    /// [`MultiKeyLock::try_lock_now`] -- returns `None` immediately
+    /// instead of waiting if the key is already held.
+    pub async fn try_with_lock_now<K, F, Fut, T>(&self, key: K, f: F) -> Option<T>
+    where
+        K: Into<String>,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = T>,
+    {
+        let guard = self.try_lock_now(key)?;
+        let result = f().await;
+        drop(guard);
+        Some(result)
     }
 }
 
+/// Exclusive-lock guard. Dropping it releases the key and wakes any
+/// pending readers or writers.
 #[derive(Debug)]
 pub struct KeyLock {
-    map: Arc<DashMap<String, u64>>,
+    map: Arc<DashMap<String, LockState>>,
     pub key: String,
     token_id: u64,
+    lease: Option<Lease>,
+}
+
+impl KeyLock {
+    /// Pushes this guard's lease deadline back by `ttl`, so the reaper
+    /// won't evict the key until `ttl` elapses from now. A no-op if the
+    /// guard was acquired via a lease-less method such as
+    /// [`MultiKeyLock::lock`].
+    pub fn renew(&self, ttl: Duration) {
+        if let Some(lease) = &self.lease {
+            let _ = lease.commands.send(ReaperCommand::Renew {
+                token_id: lease.token_id,
+                ttl,
+            });
+        }
+    }
 }
 
 impl Drop for KeyLock {
     fn drop(&mut self) {
-        self.map.remove_if(&self.key, |_, v| *v == self.token_id);
+        if let Some(lease) = self.lease.take() {
+            let _ = lease.commands.send(ReaperCommand::Cancel {
+                token_id: lease.token_id,
+            });
+        }
+
+        if let Entry::Occupied(e) = self.map.entry(self.key.clone()) {
+            let is_ours =
+                matches!(e.get(), LockState::Exclusive { token_id, .. } if *token_id == self.token_id);
+            if is_ours {
+                let (_, state) = e.remove_entry();
+                state.notify().notify_waiters();
+            }
+        }
+    }
+}
+
+/// A lease bound to a [`KeyLock`], tracked by the background reaper so an
+/// abandoned guard (owning task panicked or was aborted) is evicted once
+/// its TTL elapses instead of wedging the key forever. Identified by
+/// `token_id` rather than the reaper's internal `delay_queue::Key` --
+/// `DelayQueue` recycles slab slots, so a `delay_queue::Key` can outlive
+/// the lease it was issued for and end up aliasing a later one, while
+/// `token_id` is globally unique and never reused.
+#[derive(Debug)]
+struct Lease {
+    token_id: u64,
+    commands: mpsc::UnboundedSender<ReaperCommand>,
+}
+
+/// Messages sent to [`run_reaper`] to track or cancel a lease's deadline,
+/// addressed by the lock's `token_id` rather than the reaper's internal
+/// `delay_queue::Key` so a stale message can't alias a different lease.
+#[derive(Debug)]
+enum ReaperCommand {
+    Insert {
+        key: String,
+        token_id: u64,
+        ttl: Duration,
+    },
+    Renew {
+        token_id: u64,
+        ttl: Duration,
+    },
+    Cancel {
+        token_id: u64,
+    },
+}
+
+/// Background task owned by [`MultiKeyLock`] that evicts leased keys whose
+/// holder never released them. One reaper serves every key, driven by a
+/// single `DelayQueue` of `(key, token_id)` deadlines; `by_token` maps
+/// each live lease's `token_id` to its slot in the queue so `Renew` and
+/// `Cancel` never have to trust a recycled `delay_queue::Key`.
+async fn run_reaper(
+    locks: Arc<DashMap<String, LockState>>,
+    mut commands: mpsc::UnboundedReceiver<ReaperCommand>,
+) {
+    let mut deadlines: DelayQueue<(String, u64)> = DelayQueue::new();
+    let mut by_token: HashMap<u64, delay_queue::Key> = HashMap::new();
+
+    loop {
+        select! {
+            cmd = commands.recv() => {
+                match cmd {
+                    Some(ReaperCommand::Insert { key, token_id, ttl }) => {
+                        let delay_key = deadlines.insert((key, token_id), ttl);
+                        by_token.insert(token_id, delay_key);
+                    }
+                    Some(ReaperCommand::Renew { token_id, ttl }) => {
+                        if let Some(delay_key) = by_token.get(&token_id) {
+                            deadlines.reset(delay_key, ttl);
+                        }
+                    }
+                    Some(ReaperCommand::Cancel { token_id }) => {
+                        if let Some(delay_key) = by_token.remove(&token_id) {
+                            deadlines.try_remove(&delay_key);
+                        }
+                    }
+                    // The `MultiKeyLock` was dropped and closed the
+                    // channel -- nothing left to reap.
+                    None => return,
+                }
+            }
+            expired = poll_fn(|cx| deadlines.poll_expired(cx)), if !deadlines.is_empty() => {
+                if let Some(expired) = expired {
+                    let (key, token_id) = expired.into_inner();
+                    by_token.remove(&token_id);
+
+                    // Only the lease's own token is evicted: if the key
+                    // was released and re-acquired before the deadline
+                    // fired, this `token_id` no longer matches and the
+                    // new holder is left untouched.
+                    let evicted = locks.remove_if(&key, |_, state| {
+                        matches!(state, LockState::Exclusive { token_id: t, .. } if *t == token_id)
+                    });
+                    if let Some((_, state)) = evicted {
+                        state.notify().notify_waiters();
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Alias for the exclusive guard, named to pair with [`ReadKeyLock`].
+pub type WriteKeyLock = KeyLock;
+
+/// Shared-lock guard. Dropping it removes this reader; once the last
+/// reader for a key drops, the key is freed for a waiting writer.
+#[derive(Debug)]
+pub struct ReadKeyLock {
+    map: Arc<DashMap<String, LockState>>,
+    pub key: String,
+    token_id: u64,
+}
+
+impl Drop for ReadKeyLock {
+    fn drop(&mut self) {
+        if let Entry::Occupied(mut e) = self.map.entry(self.key.clone()) {
+            let (last_reader, has_waiting_writers) = match e.get_mut() {
+                LockState::Shared {
+                    token_ids,
+                    waiting_writers,
+                    ..
+                } => {
+                    token_ids.remove(&self.token_id);
+                    (token_ids.is_empty(), !waiting_writers.is_empty())
+                }
+                LockState::Exclusive { .. } => (false, false),
+            };
+            if last_reader {
+                if has_waiting_writers {
+                    // Leave the now reader-less `Shared` entry in place
+                    // instead of removing it -- a parked writer is still
+                    // holding a claim on this key via `waiting_writers`,
+                    // and removing the entry here would open a window for
+                    // a racing new reader's `Entry::Vacant` insert to
+                    // recreate it from scratch with that claim forgotten,
+                    // starving the writer under continuous read traffic.
+                    e.get().notify().notify_waiters();
+                } else {
+                    let (_, state) = e.remove_entry();
+                    state.notify().notify_waiters();
+                }
+            }
+        }
+    }
+}
+
+/// Guard returned by [`MultiKeyLock::lock_many`], holding every key in
+/// the set. Dropping it releases them in reverse acquisition order.
+#[derive(Debug)]
+pub struct MultiGuard {
+    guards: Vec<KeyLock>,
+}
+
+impl Drop for MultiGuard {
+    fn drop(&mut self) {
+        while let Some(guard) = self.guards.pop() {
+            drop(guard);
+        }
     }
 }
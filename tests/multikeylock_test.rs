@@ -1,4 +1,8 @@
 use multikeylock::multikeylock::{Config, MultiKeyLock};
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
 use std::time::Duration;
 use tokio::time::{sleep, Instant};
 use tokio_util::sync::CancellationToken;
@@ -7,7 +11,6 @@ use tokio_util::sync::CancellationToken;
 async fn test_lock() {
     let lock = MultiKeyLock::with_config(Config {
         timeout: Some(Duration::from_millis(100)),
-        retry: Some(Duration::from_millis(10)),
         map: Default::default(),
     });
 
@@ -117,10 +120,9 @@ async fn test_lock_with_token() {
 }
 
 #[tokio::test]
-async fn test_lock_precise_retry_steps() {
+async fn test_lock_wakes_immediately_on_release() {
     let lock = MultiKeyLock::with_config(Config {
         timeout: Some(Duration::from_secs(5)),
-        retry: Some(Duration::from_millis(10)),
         map: Default::default(),
     });
 
@@ -138,15 +140,10 @@ async fn test_lock_precise_retry_steps() {
         drop(guard1);
     });
 
-    // Second lock attempts to acquire with exponential backoff:
-    //   - Try 0: immediately → fail → retry = 10ms → sleep until 10ms
-    //   - Try 1:     at 10ms → fail → retry = 20ms → sleep until 30ms
-    //   - Try 2:     at 30ms → fail → retry = 40ms → sleep until 70ms
-    //   - Try 3:     at 70ms → fail → retry = 80ms → sleep until 150ms
-    //
-    // The lock should be acquired at ~150ms.
-    // The next retry (if needed) would sleep until 310ms (retry = 160ms).
-    // Thus, we assert the elapsed time falls between 150ms and 310ms.
+    // The waiter should be woken by `notify_waiters()` right after the
+    // release, not after sitting out a fallback/backoff sleep. Allow some
+    // slack above the 100ms release mark, but well under the old polling
+    // fallback window.
     let test_start = Instant::now();
     let handle = tokio::spawn(async move {
         let guard = lock.lock(key).await;
@@ -157,13 +154,13 @@ async fn test_lock_precise_retry_steps() {
     // --------------------------------------------------------------------------------------------
 
     let (guard2, elapsed) = handle.await.unwrap();
-    assert!(guard2.is_some(), "Expected reacquire after retry");
+    assert!(guard2.is_some(), "Expected reacquire after release");
 
     // --------------------------------------------------------------------------------------------
 
     assert!(
-        elapsed >= Duration::from_millis(150) && elapsed <= Duration::from_millis(310),
-        "Expected reacquire time between 150ms and 310ms, got {:?}",
+        elapsed >= Duration::from_millis(100) && elapsed <= Duration::from_millis(250),
+        "Expected near-immediate reacquire after release, got {:?}",
         elapsed
     );
 }
@@ -192,3 +189,411 @@ async fn test_try_lock_now() {
     let guard3 = lock.try_lock_now(key);
     assert!(guard3.is_some(), "Expected to acquire after release");
 }
+
+#[tokio::test]
+async fn test_concurrent_readers_share_lock() {
+    let lock = MultiKeyLock::new();
+    let key = "test-key";
+
+    // --------------------------------------------------------------------------------------------
+
+    let reader1 = lock.try_read_now(key);
+    assert!(reader1.is_some(), "Expected first reader to acquire");
+
+    // --------------------------------------------------------------------------------------------
+
+    let reader2 = lock.try_read_now(key);
+    assert!(reader2.is_some(), "Expected second reader to acquire concurrently");
+
+    // --------------------------------------------------------------------------------------------
+
+    assert!(
+        lock.try_write_now(key).is_none(),
+        "Expected writer to be blocked while readers hold the key"
+    );
+
+    // --------------------------------------------------------------------------------------------
+
+    drop(reader1);
+    assert!(
+        lock.try_write_now(key).is_none(),
+        "Expected writer to still be blocked by the remaining reader"
+    );
+
+    // --------------------------------------------------------------------------------------------
+
+    drop(reader2);
+    assert!(
+        lock.try_write_now(key).is_some(),
+        "Expected writer to acquire once all readers have dropped"
+    );
+}
+
+#[tokio::test]
+async fn test_writer_blocks_new_readers() {
+    let lock = MultiKeyLock::new();
+    let key = "test-key";
+
+    // --------------------------------------------------------------------------------------------
+
+    let writer = lock.try_write_now(key);
+    assert!(writer.is_some(), "Expected writer to acquire");
+
+    // --------------------------------------------------------------------------------------------
+
+    assert!(
+        lock.try_read_now(key).is_none(),
+        "Expected reader to be blocked while a writer holds the key"
+    );
+
+    // --------------------------------------------------------------------------------------------
+
+    drop(writer);
+    assert!(
+        lock.try_read_now(key).is_some(),
+        "Expected reader to acquire after the writer released"
+    );
+}
+
+#[tokio::test]
+async fn test_write_lock_waits_for_readers_to_drain() {
+    let lock = MultiKeyLock::with_config(Config {
+        timeout: Some(Duration::from_secs(5)),
+        map: Default::default(),
+    });
+
+    let key = "test-key";
+
+    // --------------------------------------------------------------------------------------------
+
+    let reader = lock.read_lock(key).await;
+    assert!(reader.is_some(), "Expected reader to acquire");
+
+    // --------------------------------------------------------------------------------------------
+
+    tokio::spawn(async move {
+        sleep(Duration::from_millis(100)).await;
+        drop(reader);
+    });
+
+    // --------------------------------------------------------------------------------------------
+
+    let start = Instant::now();
+    let writer = lock.write_lock(key).await;
+    let elapsed = start.elapsed();
+
+    assert!(writer.is_some(), "Expected writer to acquire after reader drained");
+    assert!(
+        elapsed >= Duration::from_millis(100),
+        "Expected writer to wait for the reader to drop, got {:?}",
+        elapsed
+    );
+}
+
+#[tokio::test]
+async fn test_waiting_writer_blocks_new_readers_from_joining() {
+    let lock = Arc::new(MultiKeyLock::with_config(Config {
+        timeout: Some(Duration::from_secs(5)),
+        map: Default::default(),
+    }));
+
+    let key = "test-key";
+
+    // --------------------------------------------------------------------------------------------
+
+    // An in-flight reader the writer must wait out, dropped only after
+    // a delay -- while it's held, new readers keep churning in behind
+    // it for the whole test. Without write preference those new readers
+    // would keep the count above zero forever and starve the writer.
+    let anchor = lock.read_lock(key).await;
+    assert!(anchor.is_some(), "Expected anchor reader to acquire");
+    tokio::spawn(async move {
+        sleep(Duration::from_millis(150)).await;
+        drop(anchor);
+    });
+
+    let churn_lock = lock.clone();
+    let churn_key = key.to_string();
+    let keep_churning = Arc::new(AtomicBool::new(true));
+    let keep_churning_clone = keep_churning.clone();
+    let churner = tokio::spawn(async move {
+        while keep_churning_clone.load(Ordering::Relaxed) {
+            let reader = churn_lock.try_read_now(churn_key.as_str());
+            sleep(Duration::from_millis(1)).await;
+            drop(reader);
+        }
+    });
+
+    // --------------------------------------------------------------------------------------------
+
+    let writer = lock
+        .write_lock_with_timeout(key, Duration::from_millis(500))
+        .await;
+
+    keep_churning.store(false, Ordering::Relaxed);
+    churner.await.unwrap();
+
+    assert!(
+        writer.is_some(),
+        "Expected a waiting writer to eventually acquire instead of starving behind new readers"
+    );
+}
+
+#[tokio::test]
+async fn test_lease_expires_abandoned_lock() {
+    let lock = MultiKeyLock::new();
+    let key = "test-key";
+
+    // --------------------------------------------------------------------------------------------
+
+    let guard = lock
+        .lock_with_lease(key, Duration::from_millis(100))
+        .await;
+    assert!(guard.is_some(), "Expected to acquire leased lock");
+
+    // Leak the guard without running its `Drop` -- this is the
+    // abandoned-task case the lease is meant to heal.
+    std::mem::forget(guard);
+
+    // --------------------------------------------------------------------------------------------
+
+    assert!(
+        lock.try_lock_now(key).is_none(),
+        "Expected key to still be held before the lease expires"
+    );
+
+    sleep(Duration::from_millis(200)).await;
+
+    assert!(
+        lock.try_lock_now(key).is_some(),
+        "Expected reaper to evict the abandoned lease"
+    );
+}
+
+#[tokio::test]
+async fn test_lease_renew_pushes_deadline_back() {
+    let lock = MultiKeyLock::new();
+    let key = "test-key";
+
+    // --------------------------------------------------------------------------------------------
+
+    let guard = lock
+        .lock_with_lease(key, Duration::from_millis(100))
+        .await
+        .expect("Expected to acquire leased lock");
+
+    sleep(Duration::from_millis(60)).await;
+    guard.renew(Duration::from_millis(100));
+    sleep(Duration::from_millis(60)).await;
+
+    // --------------------------------------------------------------------------------------------
+
+    assert!(
+        lock.try_lock_now(key).is_none(),
+        "Expected renewed lease to still hold the key past its original deadline"
+    );
+
+    drop(guard);
+}
+
+#[tokio::test]
+async fn test_lease_drop_cancels_pending_eviction() {
+    let lock = MultiKeyLock::new();
+    let key = "test-key";
+
+    // --------------------------------------------------------------------------------------------
+
+    let guard = lock
+        .lock_with_lease(key, Duration::from_millis(100))
+        .await;
+    assert!(guard.is_some(), "Expected to acquire leased lock");
+    drop(guard);
+
+    // A normal `Drop` releases the key immediately; a later holder must
+    // not be evicted by the first lease's now-cancelled deadline.
+    let guard2 = lock.try_lock_now(key);
+    assert!(guard2.is_some(), "Expected key to be free after drop");
+
+    sleep(Duration::from_millis(200)).await;
+
+    assert!(
+        lock.try_lock_now(key).is_none(),
+        "Expected the cancelled lease not to evict the new holder"
+    );
+}
+
+#[tokio::test]
+async fn test_stale_lease_cancel_does_not_evict_reused_slot() {
+    let lock = MultiKeyLock::new();
+
+    // --------------------------------------------------------------------------------------------
+
+    let guard_a = lock
+        .lock_with_lease("key-a", Duration::from_millis(50))
+        .await
+        .expect("Expected to acquire lease A");
+
+    // Let A's lease expire while `guard_a` is still alive -- e.g. its
+    // owning task is slow to finish. The reaper frees A's `DelayQueue`
+    // slot without A's `Drop` having run yet, so a later insert (B,
+    // below) is free to recycle that same slot and `delay_queue::Key`.
+    sleep(Duration::from_millis(150)).await;
+    assert!(
+        lock.try_lock_now("key-a").is_some(),
+        "Expected A's lease to have already been reaped"
+    );
+
+    // --------------------------------------------------------------------------------------------
+
+    let guard_b = lock
+        .lock_with_lease("key-b", Duration::from_millis(200))
+        .await
+        .expect("Expected to acquire lease B");
+
+    // A's guard finally drops and sends a `Cancel` for its now-stale
+    // slot. If that slot were recycled for B, this must not cancel B's
+    // deadline and leave B held forever.
+    drop(guard_a);
+    std::mem::forget(guard_b);
+
+    sleep(Duration::from_millis(100)).await;
+    assert!(
+        lock.try_lock_now("key-b").is_none(),
+        "Expected B's lease to still be alive before its own deadline"
+    );
+
+    // --------------------------------------------------------------------------------------------
+
+    sleep(Duration::from_millis(200)).await;
+    assert!(
+        lock.try_lock_now("key-b").is_some(),
+        "Expected B's lease to eventually expire on its own schedule"
+    );
+}
+
+#[tokio::test]
+async fn test_lock_many_acquires_all_keys() {
+    let lock = MultiKeyLock::new();
+
+    // --------------------------------------------------------------------------------------------
+
+    let guard = lock
+        .lock_many(
+            ["b".to_string(), "a".to_string(), "c".to_string()],
+            Duration::from_secs(1),
+        )
+        .await;
+    assert!(guard.is_some(), "Expected to acquire every key");
+
+    // --------------------------------------------------------------------------------------------
+
+    assert!(lock.try_lock_now("a").is_none(), "Expected key \"a\" to be held");
+    assert!(lock.try_lock_now("b").is_none(), "Expected key \"b\" to be held");
+    assert!(lock.try_lock_now("c").is_none(), "Expected key \"c\" to be held");
+
+    // --------------------------------------------------------------------------------------------
+
+    drop(guard);
+
+    assert!(lock.try_lock_now("a").is_some(), "Expected key \"a\" to be free after drop");
+    assert!(lock.try_lock_now("b").is_some(), "Expected key \"b\" to be free after drop");
+    assert!(lock.try_lock_now("c").is_some(), "Expected key \"c\" to be free after drop");
+}
+
+#[tokio::test]
+async fn test_lock_many_rolls_back_on_timeout() {
+    let lock = MultiKeyLock::new();
+
+    // --------------------------------------------------------------------------------------------
+
+    let held = lock.try_lock_now("taken").expect("Expected to pre-acquire \"taken\"");
+
+    // --------------------------------------------------------------------------------------------
+
+    let guard = lock
+        .lock_many(
+            ["free".to_string(), "taken".to_string()],
+            Duration::from_millis(100),
+        )
+        .await;
+    assert!(guard.is_none(), "Expected lock_many to time out");
+
+    // --------------------------------------------------------------------------------------------
+
+    assert!(
+        lock.try_lock_now("free").is_some(),
+        "Expected already-acquired key to be rolled back after timeout"
+    );
+
+    drop(held);
+}
+
+#[tokio::test]
+async fn test_lock_many_deduplicates_keys() {
+    let lock = MultiKeyLock::new();
+
+    // --------------------------------------------------------------------------------------------
+
+    let guard = lock
+        .lock_many(
+            ["dup".to_string(), "dup".to_string()],
+            Duration::from_secs(1),
+        )
+        .await;
+    assert!(guard.is_some(), "Expected duplicate keys to collapse into one acquisition");
+}
+
+#[tokio::test]
+async fn test_with_lock_returns_closure_result_and_releases() {
+    let lock = MultiKeyLock::new();
+    let key = "test-key";
+
+    // --------------------------------------------------------------------------------------------
+
+    let result = lock.with_lock(key, || async { 42 }).await;
+    assert_eq!(result, Some(42), "Expected with_lock to return the closure's result");
+
+    // --------------------------------------------------------------------------------------------
+
+    assert!(
+        lock.try_lock_now(key).is_some(),
+        "Expected with_lock to release the key once the closure completes"
+    );
+}
+
+#[tokio::test]
+async fn test_with_lock_times_out_while_held() {
+    let lock = MultiKeyLock::new();
+    let key = "test-key";
+
+    // --------------------------------------------------------------------------------------------
+
+    let held = lock.try_lock_now(key).expect("Expected to pre-acquire key");
+
+    let result = lock
+        .with_lock_timeout(key, Duration::from_millis(100), || async { "unreachable" })
+        .await;
+    assert!(result.is_none(), "Expected with_lock_timeout to time out while the key is held");
+
+    drop(held);
+}
+
+#[tokio::test]
+async fn test_try_with_lock_now_fails_immediately_while_held() {
+    let lock = MultiKeyLock::new();
+    let key = "test-key";
+
+    // --------------------------------------------------------------------------------------------
+
+    let held = lock.try_lock_now(key).expect("Expected to pre-acquire key");
+
+    let result = lock.try_with_lock_now(key, || async { "unreachable" }).await;
+    assert!(result.is_none(), "Expected try_with_lock_now to fail immediately while held");
+
+    // --------------------------------------------------------------------------------------------
+
+    drop(held);
+
+    let result = lock.try_with_lock_now(key, || async { "ok" }).await;
+    assert_eq!(result, Some("ok"), "Expected try_with_lock_now to succeed once free");
+}